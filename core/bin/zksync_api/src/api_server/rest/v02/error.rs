@@ -4,14 +4,15 @@ use std::fmt::{Display, Formatter};
 // External uses
 use serde::{Deserialize, Serialize};
 use serde_repr::Serialize_repr;
+use strum::EnumCount;
 use thiserror::Error;
+use zksync_api_error_derive::ApiError;
 
 // Workspace uses
 
 // Local uses
-use crate::{api_server::tx_sender::SubmitError, fee_ticker::PriceError};
 
-#[derive(Serialize_repr, Debug, Deserialize)]
+#[derive(Serialize_repr, Debug, Deserialize, Clone, Copy, PartialEq, Eq, EnumCount)]
 #[repr(u16)]
 pub enum ErrorCode {
     UnreacheableError = 0,
@@ -36,12 +37,195 @@ pub enum ErrorCode {
     Other = 60_000,
 }
 
+/// One row of the error catalog. Note that `code` is *not* unique across
+/// rows: more than one error enum can reuse the same `ErrorCode` under a
+/// different `error_type` (e.g. `ErrorCode::StorageError` is shared by
+/// `StorageError` itself, under `"storage_error"`, and
+/// `PriceError::DBError`, under `"token_error"`), so lookups must key on
+/// the `(code, error_type)` pair, not on `code` alone.
+#[derive(Debug, Serialize)]
+pub struct ErrorCatalogEntry {
+    pub code: ErrorCode,
+    pub error_type: &'static str,
+    pub description: &'static str,
+}
+
+impl ErrorCode {
+    /// All known error codes. The `error_catalog_covers_every_code` test
+    /// checks that this list is as long as `ErrorCode::COUNT`, so a variant
+    /// left out of it fails that test instead of silently shrinking the
+    /// catalog.
+    pub fn all() -> &'static [ErrorCode] {
+        &[
+            ErrorCode::UnreacheableError,
+            ErrorCode::CoreApiError,
+            ErrorCode::TokenZeroPriceError,
+            ErrorCode::InvalidCurrency,
+            ErrorCode::InvalidBlockPosition,
+            ErrorCode::InvalidAccountIdOrAddress,
+            ErrorCode::AccountNotFound,
+            ErrorCode::TransactionNotFound,
+            ErrorCode::StorageError,
+            ErrorCode::TokenNotFound,
+            ErrorCode::ExternalApiError,
+            ErrorCode::InternalError,
+            ErrorCode::AccountCloseDisabled,
+            ErrorCode::InvalidParams,
+            ErrorCode::UnsupportedFastProcessing,
+            ErrorCode::IncorrectTx,
+            ErrorCode::TxAddError,
+            ErrorCode::InappropriateFeeToken,
+            ErrorCode::CommunicationCoreServer,
+            ErrorCode::Other,
+        ]
+    }
+}
+
+/// Canonical `(code, error_type, description)` rows backing
+/// [`error_catalog`]. This table, not `ErrorCode` alone, is the source of
+/// truth for which `error_type`s exist per code — see
+/// [`ErrorCatalogEntry`] for why a code can appear more than once.
+const ERROR_CATALOG: &[(ErrorCode, &str, &str)] = &[
+    (
+        ErrorCode::UnreacheableError,
+        "api_error",
+        "An internal invariant was violated; this should never be returned to a client",
+    ),
+    (
+        ErrorCode::CoreApiError,
+        "core_api_error",
+        "The core server returned an unexpected error",
+    ),
+    (
+        ErrorCode::TokenZeroPriceError,
+        "invalid_data_error",
+        "Cannot show price in zero price token",
+    ),
+    (
+        ErrorCode::InvalidCurrency,
+        "invalid_data_error",
+        "Cannot parse currency; only token_id and usd options are supported",
+    ),
+    (
+        ErrorCode::InvalidBlockPosition,
+        "invalid_data_error",
+        "Cannot parse block position; only block_number, last_committed, last_finalized options are supported",
+    ),
+    (
+        ErrorCode::InvalidAccountIdOrAddress,
+        "invalid_data_error",
+        "Cannot parse account id or address",
+    ),
+    (
+        ErrorCode::AccountNotFound,
+        "invalid_data_error",
+        "Account is not found",
+    ),
+    (
+        ErrorCode::TransactionNotFound,
+        "invalid_data_error",
+        "Transaction is not found",
+    ),
+    (
+        ErrorCode::StorageError,
+        "storage_error",
+        "Storage layer returned an error",
+    ),
+    (
+        ErrorCode::StorageError,
+        "token_error",
+        "Storage layer returned an error while pricing a token",
+    ),
+    (ErrorCode::TokenNotFound, "token_error", "Token is not found"),
+    (
+        ErrorCode::ExternalApiError,
+        "token_error",
+        "An external API used to price a token returned an error",
+    ),
+    (
+        ErrorCode::InternalError,
+        "submit_error",
+        "Internal error while submitting a transaction",
+    ),
+    (
+        ErrorCode::AccountCloseDisabled,
+        "submit_error",
+        "Account close is disabled",
+    ),
+    (
+        ErrorCode::InvalidParams,
+        "submit_error",
+        "Transaction parameters are invalid",
+    ),
+    (
+        ErrorCode::UnsupportedFastProcessing,
+        "submit_error",
+        "Fast processing is not supported for this transaction type",
+    ),
+    (
+        ErrorCode::IncorrectTx,
+        "submit_error",
+        "Transaction is incorrect",
+    ),
+    (
+        ErrorCode::TxAddError,
+        "submit_error",
+        "Failed to add transaction to the mempool",
+    ),
+    (
+        ErrorCode::InappropriateFeeToken,
+        "submit_error",
+        "Token is not allowed to be used for paying fees",
+    ),
+    (
+        ErrorCode::CommunicationCoreServer,
+        "submit_error",
+        "Failed to communicate with the core server",
+    ),
+    (ErrorCode::Other, "submit_error", "Unclassified submit error"),
+];
+
+/// Full catalog of error codes, error types, and descriptions, for SDK and
+/// front-end teams to consume a stable list instead of scraping this file.
+pub fn error_catalog() -> Vec<ErrorCatalogEntry> {
+    ERROR_CATALOG
+        .iter()
+        .map(|&(code, error_type, description)| ErrorCatalogEntry {
+            code,
+            error_type,
+            description,
+        })
+        .collect()
+}
+
+/// Whether an error is worth retrying. Lets clients and internal retry
+/// loops make that decision from the response alone, without hard-coding
+/// individual `ErrorCode`s.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ErrorSeverity {
+    /// Calling again may succeed, e.g. a storage or upstream API hiccup.
+    Transient,
+    /// Calling again with the same input will fail the same way, e.g.
+    /// invalid params or a tx that doesn't exist.
+    Permanent,
+    /// An internal invariant was violated; retrying won't help and the
+    /// bug should be reported.
+    Fatal,
+}
+
 /// Error object in a response
 #[derive(Debug, Serialize, Deserialize)]
 pub struct Error {
     pub error_type: String,
     pub code: ErrorCode,
     pub message: String,
+    /// Machine-readable payload with additional context about the error,
+    /// e.g. the offending token id or field name. Absent when there is
+    /// nothing more specific to report than `message`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub data: Option<serde_json::Value>,
+    pub severity: ErrorSeverity,
 }
 
 /// Trait that can be used to map custom errors to the object.
@@ -53,6 +237,25 @@ pub trait ApiError: std::fmt::Display {
     fn message(&self) -> String {
         self.to_string()
     }
+
+    /// Structured, machine-readable context for the error. Defaults to
+    /// `None`; override for variants that carry data a client can branch
+    /// on without parsing `message`.
+    fn data(&self) -> Option<serde_json::Value> {
+        None
+    }
+
+    /// Whether the caller can expect a retry to behave differently.
+    /// Defaults to `Permanent`, the safe choice for an error that hasn't
+    /// been classified: a client that blindly retries on `Transient`
+    /// should not spin on something that will never succeed.
+    fn severity(&self) -> ErrorSeverity {
+        ErrorSeverity::Permanent
+    }
+
+    fn is_retryable(&self) -> bool {
+        self.severity() == ErrorSeverity::Transient
+    }
 }
 
 impl<T> From<T> for Error
@@ -64,6 +267,8 @@ where
             error_type: t.error_type(),
             code: t.code(),
             message: t.message(),
+            data: t.data(),
+            severity: t.severity(),
         }
     }
 }
@@ -98,41 +303,34 @@ impl ApiError for UnreachableError {
     fn code(&self) -> ErrorCode {
         ErrorCode::UnreacheableError
     }
+
+    fn severity(&self) -> ErrorSeverity {
+        ErrorSeverity::Fatal
+    }
 }
 
-#[derive(Error, Debug)]
+#[derive(Error, Debug, ApiError)]
 pub enum InvalidDataError {
     #[error("Cannot show price in zero price token")]
+    #[api_error(type = "invalid_data_error", code = "TokenZeroPriceError")]
     TokenZeroPriceError,
     #[error("Cannot parse block position. There are only block_number, last_committed, last_finalized options")]
+    #[api_error(type = "invalid_data_error", code = "InvalidBlockPosition")]
     InvalidBlockPosition,
     #[error("Cannot parse account id or address")]
+    #[api_error(type = "invalid_data_error", code = "InvalidAccountIdOrAddress")]
     InvalidAccountIdOrAddress,
     #[error("Account is not found")]
+    #[api_error(type = "invalid_data_error", code = "AccountNotFound")]
     AccountNotFound,
     #[error("Cannot parse currency. There are only token_id, usd options")]
+    #[api_error(type = "invalid_data_error", code = "InvalidCurrency")]
     InvalidCurrency,
     #[error("Transaction is not found")]
+    #[api_error(type = "invalid_data_error", code = "TransactionNotFound")]
     TransactionNotFound,
 }
 
-impl ApiError for InvalidDataError {
-    fn error_type(&self) -> String {
-        String::from("invalid_data_error")
-    }
-
-    fn code(&self) -> ErrorCode {
-        match self {
-            Self::TokenZeroPriceError => ErrorCode::TokenZeroPriceError,
-            Self::InvalidBlockPosition => ErrorCode::InvalidBlockPosition,
-            Self::InvalidAccountIdOrAddress => ErrorCode::InvalidAccountIdOrAddress,
-            Self::AccountNotFound => ErrorCode::AccountNotFound,
-            Self::InvalidCurrency => ErrorCode::InvalidCurrency,
-            Self::TransactionNotFound => ErrorCode::TransactionNotFound,
-        }
-    }
-}
-
 #[derive(Debug)]
 pub struct StorageError(String);
 
@@ -156,6 +354,10 @@ impl ApiError for StorageError {
     fn code(&self) -> ErrorCode {
         ErrorCode::StorageError
     }
+
+    fn severity(&self) -> ErrorSeverity {
+        ErrorSeverity::Transient
+    }
 }
 
 #[derive(Debug)]
@@ -181,38 +383,153 @@ impl ApiError for CoreApiError {
     fn code(&self) -> ErrorCode {
         ErrorCode::CoreApiError
     }
-}
 
-impl ApiError for SubmitError {
-    fn error_type(&self) -> String {
-        String::from("submit_error")
+    fn severity(&self) -> ErrorSeverity {
+        ErrorSeverity::Transient
     }
+}
 
-    fn code(&self) -> ErrorCode {
-        match self {
-            Self::AccountCloseDisabled => ErrorCode::AccountCloseDisabled,
-            Self::InvalidParams(_) => ErrorCode::InvalidParams,
-            Self::UnsupportedFastProcessing => ErrorCode::UnsupportedFastProcessing,
-            Self::IncorrectTx(_) => ErrorCode::IncorrectTx,
-            Self::TxAdd(_) => ErrorCode::TxAddError,
-            Self::InappropriateFeeToken => ErrorCode::InappropriateFeeToken,
-            Self::CommunicationCoreServer(_) => ErrorCode::CommunicationCoreServer,
-            Self::Internal(_) => ErrorCode::InternalError,
-            Self::Other(_) => ErrorCode::Other,
+// `SubmitError` (in `api_server::tx_sender`) and `PriceError` (in
+// `fee_ticker`) derive `ApiError` at their own definition sites.
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn error_catalog_covers_every_code() {
+        // A new `ErrorCode` variant missing from this match is a compile
+        // error, independent of whether anyone remembered to add a row for
+        // it to `ERROR_CATALOG`.
+        fn assert_handled(code: &ErrorCode) {
+            match code {
+                ErrorCode::UnreacheableError
+                | ErrorCode::CoreApiError
+                | ErrorCode::TokenZeroPriceError
+                | ErrorCode::InvalidCurrency
+                | ErrorCode::InvalidBlockPosition
+                | ErrorCode::InvalidAccountIdOrAddress
+                | ErrorCode::AccountNotFound
+                | ErrorCode::TransactionNotFound
+                | ErrorCode::StorageError
+                | ErrorCode::TokenNotFound
+                | ErrorCode::ExternalApiError
+                | ErrorCode::InternalError
+                | ErrorCode::AccountCloseDisabled
+                | ErrorCode::InvalidParams
+                | ErrorCode::UnsupportedFastProcessing
+                | ErrorCode::IncorrectTx
+                | ErrorCode::TxAddError
+                | ErrorCode::InappropriateFeeToken
+                | ErrorCode::CommunicationCoreServer
+                | ErrorCode::Other => {}
+            }
+        }
+
+        // `ErrorCode::all()` itself is checked against `ErrorCode::COUNT`,
+        // derived independently by `strum::EnumCount` straight from the
+        // enum definition, so a variant never pushed into `all()` is caught
+        // here rather than silently missing from the catalog coverage
+        // check below.
+        assert_eq!(ErrorCode::all().len(), ErrorCode::COUNT);
+
+        let catalog = error_catalog();
+        for code in ErrorCode::all() {
+            assert_handled(code);
+            assert!(
+                catalog.iter().any(|entry| entry.code == *code),
+                "{:?} has no row in the error catalog",
+                code
+            );
+        }
+        for entry in &catalog {
+            assert!(
+                !entry.description.is_empty(),
+                "({:?}, {:?}) is missing a catalog description",
+                entry.code,
+                entry.error_type
+            );
+            assert!(
+                !entry.error_type.is_empty(),
+                "{:?} is missing a catalog error_type",
+                entry.code
+            );
         }
     }
-}
 
-impl ApiError for PriceError {
-    fn error_type(&self) -> String {
-        String::from("token_error")
+    /// Every concrete `ApiError` impl's `(code(), error_type())` pair must
+    /// have a matching row in the catalog — catches the class of bug where
+    /// a code is reused by a second enum under a different `error_type`
+    /// (e.g. `ErrorCode::StorageError`, shared by `StorageError` and
+    /// `PriceError::DBError`) but the catalog only lists one of them.
+    #[test]
+    fn catalog_contains_every_concrete_error_pair() {
+        use crate::api_server::tx_sender::SubmitError;
+        use crate::fee_ticker::{PriceError, TokenId};
+
+        let samples: Vec<Box<dyn ApiError>> = vec![
+            Box::new(UnreachableError),
+            Box::new(StorageError::new("storage unavailable")),
+            Box::new(CoreApiError::new("core server unavailable")),
+            Box::new(InvalidDataError::TokenZeroPriceError),
+            Box::new(InvalidDataError::InvalidBlockPosition),
+            Box::new(InvalidDataError::InvalidAccountIdOrAddress),
+            Box::new(InvalidDataError::AccountNotFound),
+            Box::new(InvalidDataError::InvalidCurrency),
+            Box::new(InvalidDataError::TransactionNotFound),
+            Box::new(SubmitError::AccountCloseDisabled),
+            Box::new(SubmitError::InvalidParams("bad params".to_string())),
+            Box::new(SubmitError::UnsupportedFastProcessing),
+            Box::new(SubmitError::IncorrectTx("bad tx".to_string())),
+            Box::new(SubmitError::TxAdd("mempool rejected it".to_string())),
+            Box::new(SubmitError::InappropriateFeeToken),
+            Box::new(SubmitError::CommunicationCoreServer("timed out".to_string())),
+            Box::new(SubmitError::Internal("invariant violated".to_string())),
+            Box::new(SubmitError::Other("unclassified".to_string())),
+            Box::new(PriceError::TokenNotFound(TokenId(5))),
+            Box::new(PriceError::ApiError("upstream failed".to_string())),
+            Box::new(PriceError::DBError("storage unavailable".to_string())),
+        ];
+
+        let catalog = error_catalog();
+        for sample in &samples {
+            let (code, error_type) = (sample.code(), sample.error_type());
+            assert!(
+                catalog
+                    .iter()
+                    .any(|entry| entry.code == code && entry.error_type == error_type),
+                "catalog is missing ({:?}, {:?})",
+                code,
+                error_type
+            );
+        }
     }
 
-    fn code(&self) -> ErrorCode {
-        match self {
-            Self::TokenNotFound(_) => ErrorCode::TokenNotFound,
-            Self::ApiError(_) => ErrorCode::ExternalApiError,
-            Self::DBError(_) => ErrorCode::StorageError,
+    /// Asserts the checked-in `error_catalog.json` matches what
+    /// `error_catalog()` produces right now, failing on drift (a stale or
+    /// hand-edited artifact) instead of silently rewriting it. Regenerate
+    /// the file with an explicit opt-in:
+    /// `UPDATE_ERROR_CATALOG=1 cargo test -p zksync_api export_error_catalog`.
+    #[test]
+    fn export_error_catalog() {
+        let catalog = error_catalog();
+        let json = serde_json::to_string_pretty(&catalog).expect("catalog must serialize");
+        let json = format!("{}\n", json);
+        let path = std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join("error_catalog.json");
+
+        if std::env::var_os("UPDATE_ERROR_CATALOG").is_some() {
+            std::fs::write(&path, &json)
+                .unwrap_or_else(|err| panic!("failed to write {}: {}", path.display(), err));
+            return;
         }
+
+        let checked_in = std::fs::read_to_string(&path)
+            .unwrap_or_else(|err| panic!("failed to read {}: {}", path.display(), err));
+        assert_eq!(
+            checked_in,
+            json,
+            "{} is out of date; regenerate it with `UPDATE_ERROR_CATALOG=1 cargo test -p zksync_api export_error_catalog`",
+            path.display()
+        );
     }
 }
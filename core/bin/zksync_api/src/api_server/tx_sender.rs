@@ -0,0 +1,46 @@
+// Built-in uses
+
+// External uses
+use thiserror::Error;
+
+// Workspace uses
+
+// Local uses
+use crate::api_server::rest::v02::error::{ApiError, ErrorCode, ErrorSeverity};
+use zksync_api_error_derive::ApiError;
+
+/// Errors that can occur while submitting a transaction through the API.
+#[derive(Debug, Error, ApiError)]
+pub enum SubmitError {
+    #[error("Account close tx is disabled")]
+    #[api_error(type = "submit_error", code = "AccountCloseDisabled")]
+    AccountCloseDisabled,
+    #[error("Invalid params: {0}")]
+    #[api_error(type = "submit_error", code = "InvalidParams", data = "reason")]
+    InvalidParams(String),
+    #[error("Fast processing available only for 'withdraw' operation type")]
+    #[api_error(type = "submit_error", code = "UnsupportedFastProcessing")]
+    UnsupportedFastProcessing,
+    #[error("Transaction is incorrect: {0}")]
+    #[api_error(type = "submit_error", code = "IncorrectTx", data = "reason")]
+    IncorrectTx(String),
+    #[error("Failed to add transaction to the mempool: {0}")]
+    #[api_error(type = "submit_error", code = "TxAddError")]
+    TxAdd(String),
+    #[error("Chosen token is not supported for paying fees")]
+    #[api_error(type = "submit_error", code = "InappropriateFeeToken")]
+    InappropriateFeeToken,
+    #[error("Communication error with the core server: {0}")]
+    #[api_error(
+        type = "submit_error",
+        code = "CommunicationCoreServer",
+        severity = "Transient"
+    )]
+    CommunicationCoreServer(String),
+    #[error("Internal error: {0}")]
+    #[api_error(type = "submit_error", code = "InternalError", severity = "Fatal")]
+    Internal(String),
+    #[error("{0}")]
+    #[api_error(type = "submit_error", code = "Other")]
+    Other(String),
+}
@@ -0,0 +1,36 @@
+// Built-in uses
+use std::fmt::{self, Display, Formatter};
+
+// External uses
+use serde::Serialize;
+use thiserror::Error;
+
+// Workspace uses
+
+// Local uses
+use crate::api_server::rest::v02::error::{ApiError, ErrorCode, ErrorSeverity};
+use zksync_api_error_derive::ApiError;
+
+/// Numeric identifier of a token, as used when looking up its price.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct TokenId(pub u16);
+
+impl Display for TokenId {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+/// Errors that can occur while pricing a token for the fee ticker.
+#[derive(Debug, Error, ApiError)]
+pub enum PriceError {
+    #[error("Token not found: {0}")]
+    #[api_error(type = "token_error", code = "TokenNotFound", data = "token_id")]
+    TokenNotFound(TokenId),
+    #[error("Api error: {0}")]
+    #[api_error(type = "token_error", code = "ExternalApiError", severity = "Transient")]
+    ApiError(String),
+    #[error("Db error: {0}")]
+    #[api_error(type = "token_error", code = "StorageError", severity = "Transient")]
+    DBError(String),
+}
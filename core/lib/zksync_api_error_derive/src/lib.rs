@@ -0,0 +1,221 @@
+//! `#[derive(ApiError)]` generates the repetitive `ApiError` trait impl
+//! (`error_type`, `code`, and optionally `data`) for an error enum straight
+//! from per-variant `#[api_error(...)]` attributes, so a new variant can't
+//! be added without a matching `ErrorCode`.
+//!
+//! ```ignore
+//! #[derive(ApiError)]
+//! enum SubmitError {
+//!     #[api_error(type = "submit_error", code = "AccountCloseDisabled")]
+//!     AccountCloseDisabled,
+//!     #[api_error(type = "submit_error", code = "InvalidParams", data = "reason")]
+//!     InvalidParams(String),
+//! }
+//! ```
+//!
+//! `code = "..."` must name an existing `ErrorCode` variant: the macro
+//! emits it as a plain path expression, so a typo or a renamed/removed
+//! variant is a regular compile error at the derive site rather than a
+//! silent mismatch between an enum and its codes.
+//!
+//! An optional `severity = "Transient" | "Permanent" | "Fatal"` overrides
+//! `ApiError::severity` for that variant (an `ErrorSeverity` variant,
+//! subject to the same compile-time check); variants that omit it keep
+//! the trait's `Permanent` default.
+//!
+//! The generated impl refers to `ApiError`, `ErrorCode`, and `ErrorSeverity`
+//! unqualified, so the enum's module must bring them into scope, e.g.
+//! `use crate::api_server::rest::v02::error::{ApiError, ErrorCode,
+//! ErrorSeverity};`.
+
+use proc_macro::TokenStream;
+use quote::quote;
+use syn::{parse_macro_input, Data, DeriveInput, Fields, Ident, Lit, Meta, NestedMeta};
+
+#[proc_macro_derive(ApiError, attributes(api_error))]
+pub fn derive_api_error(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    let name = &input.ident;
+
+    let variants = match &input.data {
+        Data::Enum(data) => &data.variants,
+        _ => panic!("#[derive(ApiError)] only supports enums"),
+    };
+
+    let mut error_type_arms = Vec::new();
+    let mut code_arms = Vec::new();
+    let mut data_arms = Vec::new();
+    let mut severity_arms = Vec::new();
+    let mut has_severity_override = false;
+
+    for variant in variants {
+        let variant_ident = &variant.ident;
+        let attr = variant
+            .attrs
+            .iter()
+            .find(|attr| attr.path.is_ident("api_error"))
+            .unwrap_or_else(|| {
+                panic!(
+                    "variant `{}::{}` is missing an #[api_error(type = \"...\", code = \"...\")] attribute",
+                    name, variant_ident
+                )
+            });
+        let ApiErrorAttr {
+            error_type,
+            code,
+            data,
+            severity,
+        } = parse_api_error_attr(attr, name, variant_ident);
+
+        let code_ident = Ident::new(&code, variant_ident.span());
+        let severity_ident = severity.as_ref().map(|severity| {
+            has_severity_override = true;
+            Ident::new(severity, variant_ident.span())
+        });
+
+        match &variant.fields {
+            Fields::Unit => {
+                error_type_arms.push(quote! { Self::#variant_ident => #error_type.to_string(), });
+                code_arms.push(quote! { Self::#variant_ident => ErrorCode::#code_ident, });
+                data_arms.push(quote! { Self::#variant_ident => None, });
+                severity_arms.push(match &severity_ident {
+                    Some(severity_ident) => {
+                        quote! { Self::#variant_ident => ErrorSeverity::#severity_ident, }
+                    }
+                    None => quote! { Self::#variant_ident => ErrorSeverity::Permanent, },
+                });
+            }
+            Fields::Unnamed(fields) if fields.unnamed.len() == 1 => {
+                error_type_arms
+                    .push(quote! { Self::#variant_ident(..) => #error_type.to_string(), });
+                code_arms.push(quote! { Self::#variant_ident(..) => ErrorCode::#code_ident, });
+                data_arms.push(match &data {
+                    Some(field_name) => {
+                        let binding = Ident::new(field_name, variant_ident.span());
+                        quote! {
+                            Self::#variant_ident(#binding) => {
+                                Some(serde_json::json!({ #field_name: #binding }))
+                            }
+                        }
+                    }
+                    None => quote! { Self::#variant_ident(..) => None, },
+                });
+                severity_arms.push(match &severity_ident {
+                    Some(severity_ident) => {
+                        quote! { Self::#variant_ident(..) => ErrorSeverity::#severity_ident, }
+                    }
+                    None => quote! { Self::#variant_ident(..) => ErrorSeverity::Permanent, },
+                });
+            }
+            _ => panic!(
+                "variant `{}::{}` must be a unit variant or carry exactly one field",
+                name, variant_ident
+            ),
+        }
+    }
+
+    // Only emit `severity()` when at least one variant asked for a
+    // non-default classification; otherwise the trait's `Permanent`
+    // default already matches what we'd generate.
+    let severity_method = if has_severity_override {
+        quote! {
+            fn severity(&self) -> ErrorSeverity {
+                match self {
+                    #(#severity_arms)*
+                }
+            }
+        }
+    } else {
+        quote! {}
+    };
+
+    let expanded = quote! {
+        impl ApiError for #name {
+            fn error_type(&self) -> String {
+                match self {
+                    #(#error_type_arms)*
+                }
+            }
+
+            fn code(&self) -> ErrorCode {
+                match self {
+                    #(#code_arms)*
+                }
+            }
+
+            fn data(&self) -> Option<serde_json::Value> {
+                match self {
+                    #(#data_arms)*
+                }
+            }
+
+            #severity_method
+        }
+    };
+
+    expanded.into()
+}
+
+struct ApiErrorAttr {
+    error_type: String,
+    code: String,
+    data: Option<String>,
+    severity: Option<String>,
+}
+
+fn parse_api_error_attr(attr: &syn::Attribute, enum_name: &Ident, variant_name: &Ident) -> ApiErrorAttr {
+    let meta = attr.parse_meta().unwrap_or_else(|err| {
+        panic!(
+            "malformed #[api_error(...)] on `{}::{}`: {}",
+            enum_name, variant_name, err
+        )
+    });
+    let list = match meta {
+        Meta::List(list) => list,
+        _ => panic!(
+            "expected #[api_error(type = \"...\", code = \"...\")] on `{}::{}`",
+            enum_name, variant_name
+        ),
+    };
+
+    let mut error_type = None;
+    let mut code = None;
+    let mut data = None;
+    let mut severity = None;
+    for nested in list.nested {
+        let name_value = match nested {
+            NestedMeta::Meta(Meta::NameValue(name_value)) => name_value,
+            _ => continue,
+        };
+        let value = match &name_value.lit {
+            Lit::Str(s) => s.value(),
+            _ => continue,
+        };
+        if name_value.path.is_ident("type") {
+            error_type = Some(value);
+        } else if name_value.path.is_ident("code") {
+            code = Some(value);
+        } else if name_value.path.is_ident("data") {
+            data = Some(value);
+        } else if name_value.path.is_ident("severity") {
+            severity = Some(value);
+        }
+    }
+
+    ApiErrorAttr {
+        error_type: error_type.unwrap_or_else(|| {
+            panic!(
+                "#[api_error(...)] on `{}::{}` is missing `type`",
+                enum_name, variant_name
+            )
+        }),
+        code: code.unwrap_or_else(|| {
+            panic!(
+                "#[api_error(...)] on `{}::{}` is missing `code`",
+                enum_name, variant_name
+            )
+        }),
+        data,
+        severity,
+    }
+}
@@ -0,0 +1,78 @@
+//! Exercises the `data = "..."` field-binding and `severity = "..."`
+//! override codegen paths against a local stand-in for the `ApiError`
+//! trait, `ErrorCode`, and `ErrorSeverity`, independent of the main crate.
+
+use serde::Serialize;
+use zksync_api_error_derive::ApiError;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub enum ErrorCode {
+    Foo,
+    Bar,
+    Baz,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ErrorSeverity {
+    Transient,
+    Permanent,
+    Fatal,
+}
+
+pub trait ApiError: std::fmt::Display {
+    fn error_type(&self) -> String;
+
+    fn code(&self) -> ErrorCode;
+
+    fn message(&self) -> String {
+        self.to_string()
+    }
+
+    fn data(&self) -> Option<serde_json::Value> {
+        None
+    }
+
+    fn severity(&self) -> ErrorSeverity {
+        ErrorSeverity::Permanent
+    }
+}
+
+#[derive(Debug, ApiError)]
+enum SampleError {
+    #[api_error(type = "sample_error", code = "Foo")]
+    Unit,
+    #[api_error(type = "sample_error", code = "Bar", data = "reason")]
+    WithData(String),
+    #[api_error(type = "sample_error", code = "Baz", severity = "Transient")]
+    Retryable,
+}
+
+impl std::fmt::Display for SampleError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "sample error")
+    }
+}
+
+#[test]
+fn unit_variant_has_no_data_and_default_severity() {
+    let err = SampleError::Unit;
+    assert_eq!(err.code(), ErrorCode::Foo);
+    assert_eq!(err.data(), None);
+    assert_eq!(err.severity(), ErrorSeverity::Permanent);
+}
+
+#[test]
+fn data_attribute_binds_the_field_under_its_name() {
+    let err = SampleError::WithData("boom".to_string());
+    assert_eq!(err.code(), ErrorCode::Bar);
+    assert_eq!(err.data(), Some(serde_json::json!({ "reason": "boom" })));
+    assert_eq!(err.severity(), ErrorSeverity::Permanent);
+}
+
+#[test]
+fn severity_attribute_overrides_the_default() {
+    let err = SampleError::Retryable;
+    assert_eq!(err.code(), ErrorCode::Baz);
+    assert_eq!(err.data(), None);
+    assert_eq!(err.severity(), ErrorSeverity::Transient);
+}